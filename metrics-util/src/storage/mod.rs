@@ -0,0 +1,13 @@
+mod summary;
+pub use summary::{MergeError, Summary};
+#[cfg(feature = "serde")]
+pub use summary::DeserializeError;
+
+mod sync_summary;
+pub use sync_summary::{Recorder, SyncSummary};
+
+mod p2_quantile;
+pub use p2_quantile::P2Quantile;
+
+mod gk_summary;
+pub use gk_summary::GkSummary;
@@ -0,0 +1,163 @@
+/// A constant-space quantile estimator using the P² algorithm.
+///
+/// Unlike [`Summary`][crate::storage::Summary], which spends hundreds of KiB to provide
+/// relative-error guarantees across the whole range of quantiles, `P2Quantile` tracks a single
+/// quantile in `O(1)` space by maintaining only five marker heights and their positions. This
+/// makes it suitable for extremely memory-constrained agents that only need to track a handful of
+/// fixed quantiles.
+///
+/// Unlike `Summary`, P² gives no bounded relative-error guarantee on its estimate; its accuracy
+/// depends on the shape of the underlying distribution and how it evolves over time. It is meant
+/// for small, fixed quantile sets, not for dashboards that need precise, arbitrary quantiles.
+///
+/// See the [original paper][p2] by Jain and Chlamtac for the algorithm this is based on.
+///
+/// [p2]: https://doi.org/10.1145/4372.4378
+#[derive(Clone, Debug)]
+pub struct P2Quantile {
+    p: f64,
+    // Per-step increments for the desired marker positions: [0, p/2, p, (1+p)/2, 1].
+    dm: [f64; 5],
+    // Marker heights.
+    q: [f64; 5],
+    // Marker positions (as a float so the parabolic interpolation formula can operate on it
+    // directly).
+    n: [f64; 5],
+    // Desired marker positions, updated by `dm` after every sample.
+    m: [f64; 5],
+    // Buffers the first five samples, used to seed the markers.
+    seed: Vec<f64>,
+}
+
+impl P2Quantile {
+    /// Creates a new [`P2Quantile`] that estimates the given quantile `p`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `p` is not between 0.0 and 1.0, inclusive.
+    pub fn new(p: f64) -> P2Quantile {
+        assert!((0.0..=1.0).contains(&p), "p must be between 0.0 and 1.0");
+
+        P2Quantile {
+            p,
+            dm: [0.0, p / 2.0, p, (1.0 + p) / 2.0, 1.0],
+            q: [0.0; 5],
+            n: [1.0, 2.0, 3.0, 4.0, 5.0],
+            m: [1.0, 1.0 + 2.0 * p, 1.0 + 4.0 * p, 3.0 + 2.0 * p, 5.0],
+            seed: Vec::with_capacity(5),
+        }
+    }
+
+    /// Adds a sample to this estimator.
+    pub fn add(&mut self, value: f64) {
+        if self.seed.len() < 5 {
+            self.seed.push(value);
+
+            if self.seed.len() == 5 {
+                self.seed.sort_by(f64::total_cmp);
+                self.q.copy_from_slice(&self.seed);
+            }
+
+            return;
+        }
+
+        // Find the cell `k` such that `q[k] <= x < q[k+1]`, clamping `x` into `[q[0], q[4]]` and
+        // updating those extremes in the process.
+        let k = if value < self.q[0] {
+            self.q[0] = value;
+            0
+        } else if value >= self.q[4] {
+            self.q[4] = value;
+            3
+        } else {
+            let mut k = 0;
+            while k < 3 && !(self.q[k] <= value && value < self.q[k + 1]) {
+                k += 1;
+            }
+            k
+        };
+
+        for n in self.n.iter_mut().skip(k + 1) {
+            *n += 1.0;
+        }
+        for (m, dm) in self.m.iter_mut().zip(self.dm) {
+            *m += dm;
+        }
+
+        for i in 1..=3 {
+            let d = self.m[i] - self.n[i];
+
+            if (d >= 1.0 && self.n[i + 1] - self.n[i] > 1.0)
+                || (d <= -1.0 && self.n[i - 1] - self.n[i] < -1.0)
+            {
+                let d = d.signum();
+
+                let parabolic = self.q[i]
+                    + (d / (self.n[i + 1] - self.n[i - 1]))
+                        * ((self.n[i] - self.n[i - 1] + d) * (self.q[i + 1] - self.q[i])
+                            / (self.n[i + 1] - self.n[i])
+                            + (self.n[i + 1] - self.n[i] - d) * (self.q[i] - self.q[i - 1])
+                                / (self.n[i] - self.n[i - 1]));
+
+                self.q[i] = if self.q[i - 1] < parabolic && parabolic < self.q[i + 1] {
+                    parabolic
+                } else {
+                    // Parabolic interpolation would break monotonicity; fall back to a linear
+                    // update between the neighbor in the direction we're moving.
+                    let linear_neighbor = if d > 0.0 { i + 1 } else { i - 1 };
+                    self.q[i] + d * (self.q[linear_neighbor] - self.q[i]) / (self.n[linear_neighbor] - self.n[i])
+                };
+
+                self.n[i] += d;
+            }
+        }
+    }
+
+    /// Gets the estimated value at the tracked quantile.
+    ///
+    /// Returns `None` if fewer than five samples have been added.
+    pub fn quantile(&self) -> Option<f64> {
+        if self.seed.len() < 5 {
+            return None;
+        }
+
+        Some(self.q[2])
+    }
+
+    /// Gets the quantile this estimator tracks.
+    pub fn p(&self) -> f64 {
+        self.p
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::P2Quantile;
+
+    #[test]
+    fn test_empty() {
+        let p2 = P2Quantile::new(0.5);
+        assert_eq!(p2.quantile(), None);
+    }
+
+    #[test]
+    fn test_median_converges_on_uniform() {
+        let mut p2 = P2Quantile::new(0.5);
+        for i in 1..=10_000 {
+            p2.add(i as f64);
+        }
+
+        let estimate = p2.quantile().expect("estimate should be present");
+        assert!((estimate - 5000.0).abs() < 150.0, "estimate {estimate} should be near 5000.0");
+    }
+
+    #[test]
+    fn test_seeds_sorted() {
+        let mut p2 = P2Quantile::new(0.5);
+        for value in [5.0, 1.0, 4.0, 2.0, 3.0] {
+            p2.add(value);
+        }
+
+        assert_eq!(p2.quantile(), Some(3.0));
+    }
+}
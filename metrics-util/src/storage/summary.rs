@@ -1,6 +1,9 @@
-use sketches_ddsketch::{Config, DDSketch};
+use std::collections::BTreeMap;
 use std::fmt;
 
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
 /// A quantile sketch with relative-error guarantees.
 ///
 /// Based on [DDSketch][ddsketch], `Summary` provides quantiles over an arbitrary distribution of
@@ -13,9 +16,10 @@ use std::fmt;
 ///
 /// Numbers with an absolute value smaller than given `min_value` will be recognized as zeroes.
 ///
-/// Memory usage for `Summary` should be nearly identical to `DDSketch`.
-/// [`Summary::estimated_size`] provides a rough estimate of summary size based on the current
-/// values that have been added to it.
+/// Memory usage for `Summary` scales with the number of distinct logarithmic buckets populated by
+/// calls to [`Summary::add`], up to `max_buckets` each for negative and for positive values.
+/// [`Summary::estimated_size`] provides a rough
+/// estimate of summary size based on the current values that have been added to it.
 ///
 /// As mentioned above, this sketch provides relative-error guarantees across quantiles falling
 /// within 0 <= q <= 1, but trades some accuracy at the lowest quantiles as part of the collapsing
@@ -40,15 +44,295 @@ use std::fmt;
 ///
 /// [ddsketch]: https://arxiv.org/abs/1908.10693
 /// [hdrhistogram]: https://docs.rs/hdrhistogram
-#[derive(Clone)]
+#[derive(Clone, Debug)]
 pub struct Summary {
-    sketch: DDSketch,
+    config: SketchConfig,
+    bins: Bins,
+    moments: Moments,
+    min: f64,
+    max: f64,
 }
 
-impl fmt::Debug for Summary {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        // manual implementation because DDSketch does not implement Debug
-        f.debug_struct("Summary").finish_non_exhaustive()
+/// The parameters a [`Summary`] was constructed with.
+///
+/// This is tracked on `Summary` directly. [`Bins`] stores raw per-bucket counts plus, in each
+/// [`BinStore`], a copy of `max_buckets` used for collapsing; `alpha` and `min_value` here are
+/// what turn those counts back into bucket boundaries and quantile estimates.
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+struct SketchConfig {
+    alpha: f64,
+    max_buckets: u32,
+    min_value: f64,
+}
+
+impl SketchConfig {
+    /// Validates that this configuration is usable.
+    ///
+    /// This `SketchConfig` is what [`bucket_index`][Self::bucket_index] and
+    /// [`bucket_midpoint`][Self::bucket_midpoint] use to turn raw bucket indices back into values.
+    /// The only other decoded copy of a parameter is each [`BinStore`]'s `max_buckets`, which
+    /// [`Summary::from_decoded`] checks against this one, so validating the ranges here validates
+    /// the configuration that governs how the rest of a decoded [`Summary`] is interpreted.
+    #[cfg(feature = "serde")]
+    fn validate(&self) -> Result<(), DeserializeError> {
+        let is_self_consistent = self.alpha > 0.0
+            && self.alpha < 1.0
+            && self.max_buckets > 0
+            && self.min_value > 0.0
+            && self.min_value.is_finite();
+
+        if is_self_consistent {
+            Ok(())
+        } else {
+            Err(DeserializeError::InvalidConfig)
+        }
+    }
+
+    // Multiplicative bucket width: two values land in the same bucket only if their ratio is
+    // within `gamma`, which is exactly what gives `Summary` its relative-error guarantee.
+    fn gamma(&self) -> f64 {
+        (1.0 + self.alpha) / (1.0 - self.alpha)
+    }
+
+    fn bucket_index(&self, abs_value: f64) -> i32 {
+        (abs_value.ln() / self.gamma().ln()).ceil() as i32
+    }
+
+    // The midpoint of the bucket at `index`, used as the estimated value for any sample that fell
+    // into it. Halving the bucket's relative width this way keeps the worst-case error at `alpha`
+    // instead of `2 * alpha`.
+    fn bucket_midpoint(&self, index: i32) -> f64 {
+        let gamma = self.gamma();
+        2.0 * gamma.powi(index) / (gamma + 1.0)
+    }
+}
+
+/// The per-bucket sample counts backing a [`Summary`]'s quantile estimates.
+///
+/// Negative and positive values are tracked in separate [`BinStore`]s, keyed by logarithmic bucket
+/// index, plus a plain count of values recognized as zero. This is owned directly by `Summary`
+/// rather than reached into piecemeal per quantile, so that [`Summary::quantiles`] can walk it once
+/// for an entire batch of quantiles instead of re-walking it once per quantile.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+struct Bins {
+    negative: BinStore,
+    zeroes: u64,
+    positive: BinStore,
+}
+
+impl Bins {
+    fn new(config: &SketchConfig) -> Bins {
+        Bins {
+            negative: BinStore::new(config.max_buckets),
+            zeroes: 0,
+            positive: BinStore::new(config.max_buckets),
+        }
+    }
+
+    fn add(&mut self, value: f64, config: &SketchConfig) {
+        let abs_value = value.abs();
+        if abs_value < config.min_value {
+            self.zeroes += 1;
+            return;
+        }
+
+        let index = config.bucket_index(abs_value);
+        if value < 0.0 {
+            self.negative.add(index);
+        } else {
+            self.positive.add(index);
+        }
+    }
+
+    fn merge(&mut self, other: &Bins) {
+        self.zeroes += other.zeroes;
+        self.negative.merge(&other.negative);
+        self.positive.merge(&other.positive);
+    }
+
+    // Bucket estimates in ascending value order: most-negative first (descending magnitude on the
+    // negative side), then zero, then positive in ascending magnitude.
+    fn ordered_entries<'a>(
+        &'a self,
+        config: &'a SketchConfig,
+    ) -> impl Iterator<Item = (f64, u64)> + 'a {
+        let negative = self
+            .negative
+            .buckets
+            .iter()
+            .rev()
+            .map(move |(&index, &count)| (-config.bucket_midpoint(index), count));
+        let zero = std::iter::once((0.0, self.zeroes)).filter(|&(_, count)| count > 0);
+        let positive = self
+            .positive
+            .buckets
+            .iter()
+            .map(move |(&index, &count)| (config.bucket_midpoint(index), count));
+
+        negative.chain(zero).chain(positive)
+    }
+
+    fn walker<'a>(&'a self, config: &'a SketchConfig) -> BinsWalker<impl Iterator<Item = (f64, u64)> + 'a> {
+        BinsWalker::new(self.ordered_entries(config))
+    }
+
+    fn value_at_rank(&self, rank: u64, config: &SketchConfig) -> f64 {
+        self.walker(config).value_at_rank(rank)
+    }
+}
+
+/// A growable, capped store of per-bucket counts for one side (positive or negative) of a
+/// [`Summary`]'s value range.
+///
+/// When a new bucket would make the span from the lowest to the highest bucket index exceed
+/// `max_buckets`, the store collapses instead of widening: a new index below the lowest bucket is
+/// counted in the lowest (smallest-magnitude) bucket, and a new index above the window is added
+/// after the lowest bucket's count is folded into the next-lowest bucket. This is the same
+/// collapsing tradeoff `Summary`'s docs already describe for its lowest quantiles.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+struct BinStore {
+    max_buckets: u32,
+    buckets: BTreeMap<i32, u64>,
+}
+
+impl BinStore {
+    fn new(max_buckets: u32) -> BinStore {
+        BinStore { max_buckets, buckets: BTreeMap::new() }
+    }
+
+    fn add(&mut self, index: i32) {
+        self.add_weighted(index, 1);
+    }
+
+    fn add_weighted(&mut self, index: i32, weight: u64) {
+        if let Some(count) = self.buckets.get_mut(&index) {
+            *count += weight;
+            return;
+        }
+
+        if self.buckets.is_empty() {
+            self.buckets.insert(index, weight);
+            return;
+        }
+
+        let min_index = *self.buckets.keys().next().expect("checked non-empty above");
+        let max_index = *self.buckets.keys().next_back().expect("checked non-empty above");
+
+        if index < min_index {
+            let window = (max_index - index + 1) as u32;
+            if window <= self.max_buckets {
+                self.buckets.insert(index, weight);
+            } else {
+                // No room to extend the window downward; collapse into the current lowest bucket.
+                *self.buckets.entry(min_index).or_insert(0) += weight;
+            }
+        } else {
+            let window = (index - min_index + 1) as u32;
+            if window <= self.max_buckets {
+                self.buckets.insert(index, weight);
+            } else {
+                // Slide the window up: fold the lowest bucket into its neighbor to make room.
+                let second_lowest = *self.buckets.keys().nth(1).unwrap_or(&min_index);
+                let lowest_count = self.buckets.remove(&min_index).unwrap_or(0);
+                *self.buckets.entry(second_lowest).or_insert(0) += lowest_count;
+                self.buckets.insert(index, weight);
+            }
+        }
+    }
+
+    fn merge(&mut self, other: &BinStore) {
+        for (&index, &weight) in &other.buckets {
+            self.add_weighted(index, weight);
+        }
+    }
+}
+
+// Walks a [`Bins`]'s entries once, in ascending value order, answering a series of
+// non-decreasing rank queries without revisiting any entry more than once.
+struct BinsWalker<I: Iterator<Item = (f64, u64)>> {
+    iter: I,
+    cumulative: u64,
+    current_value: f64,
+    current_count: u64,
+    exhausted: bool,
+}
+
+impl<I: Iterator<Item = (f64, u64)>> BinsWalker<I> {
+    fn new(mut iter: I) -> Self {
+        match iter.next() {
+            Some((value, count)) => {
+                BinsWalker { iter, cumulative: 0, current_value: value, current_count: count, exhausted: false }
+            }
+            None => BinsWalker { iter, cumulative: 0, current_value: 0.0, current_count: 0, exhausted: true },
+        }
+    }
+
+    /// Returns the value of the bucket containing `rank`. `rank` must be non-decreasing across
+    /// calls on the same walker.
+    fn value_at_rank(&mut self, rank: u64) -> f64 {
+        while !self.exhausted && rank >= self.cumulative + self.current_count {
+            self.cumulative += self.current_count;
+            match self.iter.next() {
+                Some((value, count)) => {
+                    self.current_value = value;
+                    self.current_count = count;
+                }
+                None => self.exhausted = true,
+            }
+        }
+
+        self.current_value
+    }
+}
+
+/// Running mean/variance accumulator, updated with Welford's online algorithm.
+///
+/// This is tracked alongside the sketch, rather than derived from it, because a quantile sketch
+/// cannot recover the exact mean or variance of the underlying samples after the fact.
+#[derive(Clone, Copy, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+struct Moments {
+    count: u64,
+    mean: f64,
+    // Sum of squared differences from the running mean, i.e. `count * variance`.
+    m2: f64,
+}
+
+impl Moments {
+    fn add(&mut self, value: f64) {
+        self.count += 1;
+        let delta = value - self.mean;
+        self.mean += delta / self.count as f64;
+        let delta2 = value - self.mean;
+        self.m2 += delta * delta2;
+    }
+
+    fn sum(&self) -> f64 {
+        self.mean * self.count as f64
+    }
+
+    fn merge(&mut self, other: &Moments) {
+        if other.count == 0 {
+            return;
+        }
+        if self.count == 0 {
+            *self = *other;
+            return;
+        }
+
+        let count = self.count + other.count;
+        let delta = other.mean - self.mean;
+        let mean = self.mean + delta * (other.count as f64 / count as f64);
+        let m2 = self.m2
+            + other.m2
+            + delta * delta * (self.count as f64 * other.count as f64 / count as f64);
+
+        self.count = count;
+        self.mean = mean;
+        self.m2 = m2;
     }
 }
 
@@ -69,9 +353,16 @@ impl Summary {
     /// `min_value` controls the smallest value that will be recognized distinctly from zero.  Said
     /// another way, any value between `-min_value` and `min_value` will be counted as zero.
     pub fn new(alpha: f64, max_buckets: u32, min_value: f64) -> Summary {
-        let config = Config::new(alpha, max_buckets, min_value.abs());
-
-        Summary { sketch: DDSketch::new(config) }
+        let min_value = min_value.abs();
+        let config = SketchConfig { alpha, max_buckets, min_value };
+
+        Summary {
+            bins: Bins::new(&config),
+            config,
+            moments: Moments::default(),
+            min: f64::INFINITY,
+            max: f64::NEG_INFINITY,
+        }
     }
 
     /// Creates a new [`Summary`] with default values.
@@ -96,7 +387,10 @@ impl Summary {
             return;
         }
 
-        self.sketch.add(value);
+        self.bins.add(value, &self.config);
+        self.moments.add(value);
+        self.min = self.min.min(value);
+        self.max = self.max.max(value);
     }
 
     /// Gets the estimated value at the given quantile.
@@ -107,11 +401,58 @@ impl Summary {
     /// If the 0.0 or 1.0 quantile is requested, this function will return self.min() or self.max()
     /// instead of the estimated value.
     pub fn quantile(&self, q: f64) -> Option<f64> {
-        if !(0.0..=1.0).contains(&q) || self.count() == 0 {
+        if !(0.0..=1.0).contains(&q) || self.is_empty() {
             return None;
         }
 
-        self.sketch.quantile(q).expect("quantile should be valid at this point")
+        if q == 0.0 {
+            return Some(self.min);
+        }
+        if q == 1.0 {
+            return Some(self.max);
+        }
+
+        let target_rank = (q * (self.count() as f64 - 1.0)).floor() as u64;
+        Some(self.bins.value_at_rank(target_rank, &self.config))
+    }
+
+    /// Gets the estimated values at each of the given quantiles, e.g. p50/p90/p95/p99/p999.
+    ///
+    /// Unlike calling [`quantile`][Self::quantile] once per entry in `qs`, this walks the bin
+    /// stores backing this summary exactly once, in ascending value order, so the cost of the walk
+    /// is amortized across the whole batch rather than repeated per quantile.
+    ///
+    /// The result is in the same order as `qs`. As with `quantile`, an entry is `None` if the
+    /// sketch is empty or if the corresponding quantile is less than 0.0 or greater than 1.0.
+    pub fn quantiles<I: IntoIterator<Item = f64>>(&self, qs: I) -> Vec<Option<f64>> {
+        let qs: Vec<f64> = qs.into_iter().collect();
+
+        if self.is_empty() {
+            return qs.iter().map(|_| None).collect();
+        }
+
+        let n = self.count() as f64;
+
+        // Visit queries in ascending order of quantile so the walk below only ever moves forward,
+        // then scatter the answers back into the caller's original order.
+        let mut order: Vec<usize> = (0..qs.len()).filter(|&i| (0.0..=1.0).contains(&qs[i])).collect();
+        order.sort_by(|&a, &b| qs[a].total_cmp(&qs[b]));
+
+        let mut results = vec![None; qs.len()];
+        let mut walker = self.bins.walker(&self.config);
+        for i in order {
+            let q = qs[i];
+            results[i] = Some(if q == 0.0 {
+                self.min
+            } else if q == 1.0 {
+                self.max
+            } else {
+                let target_rank = (q * (n - 1.0)).floor() as u64;
+                walker.value_at_rank(target_rank)
+            });
+        }
+
+        results
     }
 
     /// Merge another Summary into this one.
@@ -121,18 +462,29 @@ impl Summary {
     /// This function will return an error if the other Summary was not created with the same
     /// parameters.
     pub fn merge(&mut self, other: &Summary) -> Result<(), MergeError> {
-        self.sketch.merge(&other.sketch).map_err(|_| MergeError {})?;
+        if self.config.alpha != other.config.alpha
+            || self.config.max_buckets != other.config.max_buckets
+            || self.config.min_value != other.config.min_value
+        {
+            return Err(MergeError {});
+        }
+
+        self.bins.merge(&other.bins);
+        self.moments.merge(&other.moments);
+        self.min = self.min.min(other.min);
+        self.max = self.max.max(other.max);
+
         Ok(())
     }
 
     /// Gets the minimum value this summary has seen so far.
     pub fn min(&self) -> f64 {
-        self.sketch.min().unwrap_or(f64::INFINITY)
+        self.min
     }
 
     /// Gets the maximum value this summary has seen so far.
     pub fn max(&self) -> f64 {
-        self.sketch.max().unwrap_or(f64::NEG_INFINITY)
+        self.max
     }
 
     /// Whether or not this summary is empty.
@@ -142,7 +494,48 @@ impl Summary {
 
     /// Gets the number of samples in this summary.
     pub fn count(&self) -> usize {
-        self.sketch.count()
+        self.moments.count as usize
+    }
+
+    /// Gets the sum of all samples added to this summary.
+    ///
+    /// Returns `None` if the summary is empty.
+    pub fn sum(&self) -> Option<f64> {
+        if self.is_empty() {
+            return None;
+        }
+
+        Some(self.moments.sum())
+    }
+
+    /// Gets the arithmetic mean of all samples added to this summary.
+    ///
+    /// Returns `None` if the summary is empty.
+    pub fn mean(&self) -> Option<f64> {
+        if self.is_empty() {
+            return None;
+        }
+
+        Some(self.moments.mean)
+    }
+
+    /// Gets the variance of all samples added to this summary.
+    ///
+    /// Returns `None` if the summary is empty. Computed with Welford's online algorithm, which
+    /// remains numerically stable even for large-magnitude samples.
+    pub fn variance(&self) -> Option<f64> {
+        if self.is_empty() {
+            return None;
+        }
+
+        Some(self.moments.m2 / self.moments.count as f64)
+    }
+
+    /// Gets the standard deviation of all samples added to this summary.
+    ///
+    /// Returns `None` if the summary is empty.
+    pub fn std_dev(&self) -> Option<f64> {
+        self.variance().map(f64::sqrt)
     }
 
     /// Gets the estimized size of this summary, in bytes.
@@ -150,10 +543,112 @@ impl Summary {
     /// In practice, this value should be very close to the actual size, but will not be entirely
     /// precise.
     pub fn estimated_size(&self) -> usize {
-        std::mem::size_of::<Self>() + (self.sketch.length() * 8)
+        let bucket_entries = self.bins.negative.buckets.len() + self.bins.positive.buckets.len();
+        std::mem::size_of::<Self>() + bucket_entries * std::mem::size_of::<(i32, u64)>()
+    }
+
+    /// Serializes this summary into an opaque byte buffer.
+    ///
+    /// The resulting buffer encodes the summary's configuration (`alpha`, `max_buckets`, and
+    /// `min_value`) alongside its positive and negative bin stores and the zero/count/min/max
+    /// bookkeeping, and can be shipped to another process and fed into [`Summary::deserialize`] to
+    /// reconstruct an equivalent summary. This allows a central aggregator to
+    /// [`merge`][Summary::merge] sketches that were produced independently on many nodes, which is
+    /// the classic distributed-quantile use case that DDSketch was designed for.
+    ///
+    /// Requires the `serde` feature.
+    #[cfg(feature = "serde")]
+    pub fn serialize(&self) -> Vec<u8> {
+        bincode::serialize(&(&self.config, &self.bins, &self.moments, self.min, self.max))
+            .expect("serializing an in-memory summary should never fail")
+    }
+
+    /// Deserializes a summary from a byte buffer produced by [`Summary::serialize`].
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if `bytes` cannot be decoded, or if the encoded
+    /// configuration is not self-consistent, rather than panicking on truncated or corrupt input.
+    ///
+    /// Requires the `serde` feature.
+    #[cfg(feature = "serde")]
+    pub fn deserialize(bytes: &[u8]) -> Result<Summary, DeserializeError> {
+        let (config, bins, moments, min, max): (SketchConfig, Bins, Moments, f64, f64) =
+            bincode::deserialize(bytes).map_err(|e| DeserializeError::Decode(e.to_string()))?;
+
+        Summary::from_decoded(config, bins, moments, min, max)
+    }
+
+    /// Builds a [`Summary`] from its decoded parts, validating the configuration in one place so
+    /// that both [`Summary::deserialize`] and the `serde::Deserialize` impl reject inconsistent
+    /// input rather than panicking or skipping validation.
+    #[cfg(feature = "serde")]
+    fn from_decoded(
+        config: SketchConfig,
+        bins: Bins,
+        moments: Moments,
+        min: f64,
+        max: f64,
+    ) -> Result<Summary, DeserializeError> {
+        config.validate()?;
+        if bins.negative.max_buckets != config.max_buckets
+            || bins.positive.max_buckets != config.max_buckets
+        {
+            return Err(DeserializeError::InvalidConfig);
+        }
+
+        Ok(Summary { config, bins, moments, min, max })
+    }
+}
+
+#[cfg(feature = "serde")]
+impl Serialize for Summary {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        (&self.config, &self.bins, &self.moments, self.min, self.max).serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for Summary {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let (config, bins, moments, min, max) =
+            <(SketchConfig, Bins, Moments, f64, f64)>::deserialize(deserializer)?;
+
+        Summary::from_decoded(config, bins, moments, min, max).map_err(serde::de::Error::custom)
+    }
+}
+
+/// Errors that can occur when deserializing a [`Summary`] via [`Summary::deserialize`].
+#[cfg(feature = "serde")]
+#[derive(Debug)]
+pub enum DeserializeError {
+    /// The byte buffer could not be decoded.
+    Decode(String),
+    /// The encoded sketch configuration is not self-consistent.
+    InvalidConfig,
+}
+
+#[cfg(feature = "serde")]
+impl fmt::Display for DeserializeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DeserializeError::Decode(reason) => write!(f, "failed to decode summary: {reason}"),
+            DeserializeError::InvalidConfig => {
+                write!(f, "encoded summary configuration is not self-consistent")
+            }
+        }
     }
 }
 
+#[cfg(feature = "serde")]
+impl std::error::Error for DeserializeError {}
+
 #[derive(Copy, Clone, Debug)]
 pub struct MergeError {}
 
@@ -330,6 +825,104 @@ mod tests {
         assert_eq!(summary.quantile(0.5), None);
     }
 
+    #[test]
+    fn test_quantiles_matches_individual_calls() {
+        let mut summary = Summary::with_defaults();
+        for value in [-420.42, 420.42, 42.42, 0.0] {
+            summary.add(value);
+        }
+
+        let qs = vec![0.99, 0.0, 0.5, 1.0, 1.5, 0.1];
+        let batched = summary.quantiles(qs.clone());
+        let individual: Vec<Option<f64>> = qs.iter().map(|q| summary.quantile(*q)).collect();
+
+        assert_eq!(batched, individual);
+    }
+
+    #[test]
+    fn test_quantiles_empty() {
+        let summary = Summary::with_defaults();
+        assert_eq!(summary.quantiles([0.5, 0.9]), vec![None, None]);
+    }
+
+    #[test]
+    fn test_moments() {
+        let mut summary = Summary::with_defaults();
+        assert_eq!(summary.sum(), None);
+        assert_eq!(summary.mean(), None);
+        assert_eq!(summary.variance(), None);
+        assert_eq!(summary.std_dev(), None);
+
+        let values = [2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0];
+        for value in values {
+            summary.add(value);
+        }
+
+        assert_relative_eq!(summary.sum().unwrap(), values.iter().sum::<f64>());
+        assert_relative_eq!(summary.mean().unwrap(), 5.0);
+        assert_relative_eq!(summary.variance().unwrap(), 4.0);
+        assert_relative_eq!(summary.std_dev().unwrap(), 2.0);
+    }
+
+    #[test]
+    fn test_moments_merge() {
+        let mut left = Summary::with_defaults();
+        for value in [2.0, 4.0, 4.0] {
+            left.add(value);
+        }
+
+        let mut right = Summary::with_defaults();
+        for value in [4.0, 5.0, 5.0, 7.0, 9.0] {
+            right.add(value);
+        }
+
+        left.merge(&right).expect("summaries should be mergeable");
+
+        let mut whole = Summary::with_defaults();
+        for value in [2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0] {
+            whole.add(value);
+        }
+
+        assert_relative_eq!(left.sum().unwrap(), whole.sum().unwrap());
+        assert_relative_eq!(left.mean().unwrap(), whole.mean().unwrap());
+        assert_relative_eq!(left.variance().unwrap(), whole.variance().unwrap());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serialize_roundtrip() {
+        let mut summary = Summary::with_defaults();
+        for value in [-420.42, 420.42, 42.42, 0.0] {
+            summary.add(value);
+        }
+
+        let bytes = summary.serialize();
+        let roundtripped = Summary::deserialize(&bytes).expect("bytes should decode");
+
+        assert_eq!(roundtripped.count(), summary.count());
+        assert_relative_eq!(roundtripped.min(), summary.min());
+        assert_relative_eq!(roundtripped.max(), summary.max());
+        for q in [0.1, 0.5, 0.9] {
+            assert_relative_eq!(
+                roundtripped.quantile(q).expect("value should exist"),
+                summary.quantile(q).expect("value should exist")
+            );
+        }
+
+        // A summary created with different parameters should not be mergeable with one
+        // deserialized from a summary created with the defaults.
+        let mut other = Summary::new(0.001, 2048, 1.0e-6);
+        other.add(1.0);
+        assert!(roundtripped.clone().merge(&other).is_err());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_deserialize_corrupt_input_does_not_panic() {
+        let result = Summary::deserialize(&[0xFF, 0x00, 0x01]);
+        assert!(result.is_err());
+    }
+
     #[quickcheck]
     fn quantile_validity(inputs: Vec<f64>) -> bool {
         let mut had_non_inf = false;
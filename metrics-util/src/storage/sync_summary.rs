@@ -0,0 +1,274 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use crossbeam_queue::SegQueue;
+
+use super::Summary;
+
+/// A recorder handle used to feed samples into a [`SyncSummary`], freely shareable across threads.
+///
+/// A `Recorder` is obtained via [`SyncSummary::recorder`] and can be freely cloned to hand out to
+/// multiple threads, including calling [`record`][Self::record] on clones of the same `Recorder`
+/// concurrently. Recording a sample never blocks and never contends with other recorders or with
+/// the owning [`SyncSummary`]; samples are simply buffered until the owner calls
+/// [`SyncSummary::refresh`].
+#[derive(Clone)]
+pub struct Recorder {
+    state: Arc<RecorderState>,
+}
+
+impl Recorder {
+    /// Records a sample.
+    ///
+    /// This buffers `value` locally and never touches the shared [`Summary`] backing the owning
+    /// [`SyncSummary`]; it will not be visible until the next [`SyncSummary::refresh`].
+    pub fn record(&self, value: f64) {
+        // Tracking the number of in-flight pushes (rather than a single parity bit) lets a
+        // concurrent `drain` tell writes are outstanding even when two clones of this `Recorder`
+        // push from different threads at the same time; a parity bit can land back on "even"
+        // while one of several overlapping pushes is still in progress.
+        self.state.in_flight.fetch_add(1, Ordering::AcqRel);
+        self.state.buffer.push(value);
+        self.state.in_flight.fetch_sub(1, Ordering::AcqRel);
+    }
+}
+
+impl std::fmt::Debug for Recorder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Recorder").finish_non_exhaustive()
+    }
+}
+
+#[derive(Default)]
+struct RecorderState {
+    buffer: SegQueue<f64>,
+    in_flight: AtomicUsize,
+}
+
+/// A multi-writer [`Summary`] that allows many threads to record samples without contending on a
+/// single lock.
+///
+/// Each writer thread obtains a cheap [`Recorder`] via [`SyncSummary::recorder`], which buffers
+/// samples locally without touching any shared state. The owner periodically calls
+/// [`SyncSummary::refresh`] to drain all outstanding recorders and fold their buffered samples
+/// into the backing [`Summary`]; only `refresh` pays the cost of synchronizing with writers, so
+/// high-throughput concurrent ingestion is not serialized behind a single `Mutex`.
+///
+/// A recorder's state is kept alive past every external [`Recorder`] handle being dropped only
+/// until it is next drained, so that samples recorded right before a drop are never lost: a
+/// dropped recorder simply stops receiving new samples, but whatever it had already buffered is
+/// still folded in by the next [`refresh`][SyncSummary::refresh]. Once a drained recorder's last
+/// external handle is gone, [`refresh`][SyncSummary::refresh] also prunes its entry, so recorders
+/// created and dropped over the life of a long-running `SyncSummary` don't accumulate forever.
+pub struct SyncSummary {
+    summary: Summary,
+    recorders: Mutex<Vec<Arc<RecorderState>>>,
+}
+
+impl SyncSummary {
+    /// Creates a new [`SyncSummary`].
+    ///
+    /// See [`Summary::new`] for the meaning of `alpha`, `max_buckets`, and `min_value`.
+    pub fn new(alpha: f64, max_buckets: u32, min_value: f64) -> SyncSummary {
+        SyncSummary { summary: Summary::new(alpha, max_buckets, min_value), recorders: Mutex::new(Vec::new()) }
+    }
+
+    /// Creates a new [`SyncSummary`] with default values.
+    ///
+    /// See [`Summary::with_defaults`] for the values used.
+    pub fn with_defaults() -> SyncSummary {
+        SyncSummary { summary: Summary::with_defaults(), recorders: Mutex::new(Vec::new()) }
+    }
+
+    /// Creates a new [`Recorder`] for feeding samples into this summary.
+    ///
+    /// The returned handle may be cloned and sent to as many threads as needed; recording through
+    /// it never blocks on, or contends with, any other recorder.
+    pub fn recorder(&self) -> Recorder {
+        let state = Arc::new(RecorderState::default());
+        self.recorders.lock().unwrap().push(Arc::clone(&state));
+        Recorder { state }
+    }
+
+    /// Drains all outstanding recorders and folds their buffered samples into this summary.
+    ///
+    /// This blocks until it has synchronized with every live recorder, so that
+    /// [`quantile`][Summary::quantile], [`count`][Summary::count], [`min`][Summary::min], and
+    /// [`max`][Summary::max] reflect a consistent snapshot once it returns.
+    pub fn refresh(&mut self) {
+        self.drain(None);
+    }
+
+    /// Like [`refresh`][Self::refresh], but gives up waiting on a slow recorder after `timeout`
+    /// has elapsed, folding in whatever samples had already been synchronized.
+    pub fn refresh_timeout(&mut self, timeout: Duration) {
+        self.drain(Some(timeout));
+    }
+
+    fn drain(&mut self, timeout: Option<Duration>) {
+        let deadline = timeout.map(|timeout| Instant::now() + timeout);
+
+        let summary = &mut self.summary;
+        let mut recorders = self.recorders.lock().unwrap();
+
+        // Drain every recorder, then drop our own reference to any whose external `Recorder`
+        // handles have all been dropped: once `Arc::strong_count` is down to the one count we
+        // hold here, nothing can ever record through it again, so there's nothing left to lose by
+        // freeing it.
+        recorders.retain(|state| {
+            while state.in_flight.load(Ordering::Acquire) != 0 {
+                if let Some(deadline) = deadline {
+                    if Instant::now() >= deadline {
+                        break;
+                    }
+                }
+                std::hint::spin_loop();
+            }
+
+            while let Some(value) = state.buffer.pop() {
+                summary.add(value);
+            }
+
+            Arc::strong_count(state) > 1
+        });
+    }
+
+    /// Gets the estimated value at the given quantile.
+    ///
+    /// Only reflects samples folded in by the last call to [`refresh`][Self::refresh] or
+    /// [`refresh_timeout`][Self::refresh_timeout].
+    pub fn quantile(&self, q: f64) -> Option<f64> {
+        self.summary.quantile(q)
+    }
+
+    /// Gets the minimum value seen as of the last refresh.
+    pub fn min(&self) -> f64 {
+        self.summary.min()
+    }
+
+    /// Gets the maximum value seen as of the last refresh.
+    pub fn max(&self) -> f64 {
+        self.summary.max()
+    }
+
+    /// Gets the number of samples folded in as of the last refresh.
+    pub fn count(&self) -> usize {
+        self.summary.count()
+    }
+
+    /// Whether or not this summary is empty as of the last refresh.
+    pub fn is_empty(&self) -> bool {
+        self.summary.is_empty()
+    }
+}
+
+impl std::fmt::Debug for SyncSummary {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SyncSummary").field("summary", &self.summary).finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SyncSummary;
+    use std::thread;
+
+    #[test]
+    fn test_single_recorder_refresh() {
+        let mut sync_summary = SyncSummary::with_defaults();
+        let recorder = sync_summary.recorder();
+
+        recorder.record(1.0);
+        recorder.record(2.0);
+        recorder.record(3.0);
+
+        assert_eq!(sync_summary.count(), 0);
+        sync_summary.refresh();
+        assert_eq!(sync_summary.count(), 3);
+        assert_eq!(sync_summary.min(), 1.0);
+        assert_eq!(sync_summary.max(), 3.0);
+    }
+
+    #[test]
+    fn test_multiple_writer_threads() {
+        let mut sync_summary = SyncSummary::with_defaults();
+        let handles: Vec<_> = (0..8)
+            .map(|_| {
+                let recorder = sync_summary.recorder();
+                thread::spawn(move || {
+                    for i in 0..1000 {
+                        recorder.record(i as f64);
+                    }
+                })
+            })
+            .collect();
+
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        sync_summary.refresh();
+        assert_eq!(sync_summary.count(), 8000);
+    }
+
+    #[test]
+    fn test_dropped_recorder_samples_are_not_lost() {
+        let mut sync_summary = SyncSummary::with_defaults();
+        {
+            let recorder = sync_summary.recorder();
+            recorder.record(1.0);
+        }
+
+        // The `Recorder` handle above is gone, but its buffered sample should still be folded in:
+        // `SyncSummary` keeps the recorder's state alive until it has been drained.
+        sync_summary.refresh();
+        assert_eq!(sync_summary.count(), 1);
+
+        sync_summary.refresh();
+        assert_eq!(sync_summary.count(), 1);
+    }
+
+    #[test]
+    fn test_dropped_recorder_is_pruned_after_refresh() {
+        let mut sync_summary = SyncSummary::with_defaults();
+        {
+            let recorder = sync_summary.recorder();
+            recorder.record(1.0);
+        }
+
+        assert_eq!(sync_summary.recorders.lock().unwrap().len(), 1);
+
+        // The recorder's sample is folded in on this refresh, and since its `Recorder` handle is
+        // already gone, its now-empty entry is pruned rather than kept around forever.
+        sync_summary.refresh();
+        assert_eq!(sync_summary.count(), 1);
+        assert_eq!(sync_summary.recorders.lock().unwrap().len(), 0);
+    }
+
+    #[test]
+    fn test_concurrent_refresh_and_record() {
+        let mut sync_summary = SyncSummary::with_defaults();
+        let recorder = sync_summary.recorder();
+
+        let writer = {
+            let recorder = recorder.clone();
+            thread::spawn(move || {
+                for i in 0..10_000 {
+                    recorder.record(i as f64);
+                }
+            })
+        };
+
+        // Refresh repeatedly while the writer thread is still recording, exercising `drain`'s
+        // in-flight wait concurrently with live `record` calls on a cloned `Recorder`, rather than
+        // only after the writer has already finished.
+        while !writer.is_finished() {
+            sync_summary.refresh();
+        }
+        writer.join().unwrap();
+        sync_summary.refresh();
+
+        assert_eq!(sync_summary.count(), 10_000);
+    }
+}